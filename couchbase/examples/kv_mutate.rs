@@ -8,8 +8,9 @@ struct Airport {
     iata: String,
 }
 
-fn main() {
-    let mut cluster = Cluster::connect("couchbase://127.0.0.1", "Administrator", "password")
+#[tokio::main]
+async fn main() {
+    let cluster = Cluster::connect("couchbase://127.0.0.1", "Administrator", "password")
         .expect("Could not create Cluster reference!");
     let bucket = cluster
         .bucket("travel-sample")
@@ -23,6 +24,7 @@ fn main() {
     };
     collection
         .upsert("airport_999", airport, None)
+        .await
         .expect("could not upsert airport!");
 
     cluster.disconnect().expect("Failure while disconnecting!");
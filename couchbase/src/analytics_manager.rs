@@ -0,0 +1,454 @@
+use crate::error::CouchbaseResult;
+use crate::instance::{Instance, SharedInstance};
+use futures::StreamExt;
+use serde::Deserialize;
+use std::rc::Rc;
+use std::sync::Arc;
+
+const LIST_DATASETS_STATEMENT: &str =
+    "SELECT d.* FROM `Metadata`.`Dataset` d WHERE d.DataverseName <> \"Metadata\"";
+
+/// A dataset as reported by the `Metadata.Dataset` system dataset, see `list_datasets`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnalyticsDataset {
+    #[serde(rename = "DatasetName")]
+    name: String,
+    #[serde(rename = "DataverseName")]
+    dataverse: String,
+    #[serde(rename = "BucketName")]
+    bucket: String,
+}
+
+impl AnalyticsDataset {
+    /// The name of the dataset.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The dataverse the dataset lives in.
+    pub fn dataverse(&self) -> &str {
+        &self.dataverse
+    }
+
+    /// The name of the Couchbase bucket this dataset shadows.
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+}
+
+/// Qualifies an identifier (dataset, index owner, ...) with its dataverse, backticking both
+/// parts so names containing reserved characters stay valid N1QL identifiers.
+fn qualify(dataverse_name: Option<&str>, name: &str) -> String {
+    match dataverse_name {
+        Some(dataverse) => format!("`{}`.`{}`", dataverse, name),
+        None => format!("`{}`", name),
+    }
+}
+
+fn create_dataverse_statement(dataverse_name: &str, ignore_if_exists: bool) -> String {
+    let exists_clause = if ignore_if_exists { " IF NOT EXISTS" } else { "" };
+    format!("CREATE DATAVERSE `{}`{}", dataverse_name, exists_clause)
+}
+
+fn drop_dataverse_statement(dataverse_name: &str, ignore_if_not_exists: bool) -> String {
+    let exists_clause = if ignore_if_not_exists { " IF EXISTS" } else { "" };
+    format!("DROP DATAVERSE `{}`{}", dataverse_name, exists_clause)
+}
+
+fn create_dataset_statement(
+    dataset_name: &str,
+    bucket_name: &str,
+    dataverse_name: Option<&str>,
+    ignore_if_exists: bool,
+) -> String {
+    let exists_clause = if ignore_if_exists { " IF NOT EXISTS" } else { "" };
+    let dataset_name = qualify(dataverse_name, dataset_name);
+    format!("CREATE DATASET{} {} ON `{}`", exists_clause, dataset_name, bucket_name)
+}
+
+fn drop_dataset_statement(dataset_name: &str, dataverse_name: Option<&str>, ignore_if_not_exists: bool) -> String {
+    let exists_clause = if ignore_if_not_exists { " IF EXISTS" } else { "" };
+    let dataset_name = qualify(dataverse_name, dataset_name);
+    format!("DROP DATASET {}{}", dataset_name, exists_clause)
+}
+
+fn create_index_statement(
+    index_name: &str,
+    dataset_name: &str,
+    dataverse_name: Option<&str>,
+    fields: &[(&str, &str)],
+    ignore_if_exists: bool,
+) -> String {
+    let exists_clause = if ignore_if_exists { " IF NOT EXISTS" } else { "" };
+    let dataset_name = qualify(dataverse_name, dataset_name);
+    let fields = fields
+        .iter()
+        .map(|(name, kind)| format!("`{}`: {}", name, kind))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("CREATE INDEX `{}`{} ON {} ({})", index_name, exists_clause, dataset_name, fields)
+}
+
+fn drop_index_statement(index_name: &str, dataset_name: &str, dataverse_name: Option<&str>) -> String {
+    let dataset_name = qualify(dataverse_name, dataset_name);
+    format!("DROP INDEX {}.`{}`", dataset_name, index_name)
+}
+
+fn connect_link_statement(link_name: Option<&str>) -> String {
+    format!("CONNECT LINK {}", link_name.unwrap_or("Local"))
+}
+
+fn disconnect_link_statement(link_name: Option<&str>) -> String {
+    format!("DISCONNECT LINK {}", link_name.unwrap_or("Local"))
+}
+
+/// Provides analytics management operations (dataverses, datasets, indexes and links),
+/// reachable through `Bucket::analytics_manager`.
+///
+/// This is the DDL counterpart to `Bucket::analytics_query`: rather than hand-writing every
+/// `CREATE`/`DROP` statement, callers can drive dataset lifecycle through this API.
+pub struct AnalyticsManager {
+    instance: Rc<Instance>,
+}
+
+impl AnalyticsManager {
+    pub(crate) fn new(instance: Rc<Instance>) -> Self {
+        AnalyticsManager { instance }
+    }
+
+    /// Creates a dataverse, optionally ignoring the request if it already exists.
+    pub async fn create_dataverse<S>(&self, dataverse_name: S, ignore_if_exists: bool) -> CouchbaseResult<()>
+    where
+        S: Into<String>,
+    {
+        self.exec(create_dataverse_statement(&dataverse_name.into(), ignore_if_exists))
+            .await
+    }
+
+    /// Drops a dataverse, optionally ignoring the request if it does not exist.
+    pub async fn drop_dataverse<S>(&self, dataverse_name: S, ignore_if_not_exists: bool) -> CouchbaseResult<()>
+    where
+        S: Into<String>,
+    {
+        self.exec(drop_dataverse_statement(&dataverse_name.into(), ignore_if_not_exists))
+            .await
+    }
+
+    /// Creates a dataset shadowing `bucket_name`, optionally in a dataverse other than the
+    /// default one.
+    pub async fn create_dataset<S1, S2>(
+        &self,
+        dataset_name: S1,
+        bucket_name: S2,
+        dataverse_name: Option<&str>,
+        ignore_if_exists: bool,
+    ) -> CouchbaseResult<()>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.exec(create_dataset_statement(
+            &dataset_name.into(),
+            &bucket_name.into(),
+            dataverse_name,
+            ignore_if_exists,
+        ))
+        .await
+    }
+
+    /// Drops a dataset, optionally ignoring the request if it does not exist.
+    pub async fn drop_dataset<S>(
+        &self,
+        dataset_name: S,
+        dataverse_name: Option<&str>,
+        ignore_if_not_exists: bool,
+    ) -> CouchbaseResult<()>
+    where
+        S: Into<String>,
+    {
+        self.exec(drop_dataset_statement(&dataset_name.into(), dataverse_name, ignore_if_not_exists))
+            .await
+    }
+
+    /// Creates a secondary index on the given fields of a dataset.
+    pub async fn create_index<S1, S2>(
+        &self,
+        index_name: S1,
+        dataset_name: S2,
+        dataverse_name: Option<&str>,
+        fields: &[(&str, &str)],
+        ignore_if_exists: bool,
+    ) -> CouchbaseResult<()>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.exec(create_index_statement(
+            &index_name.into(),
+            &dataset_name.into(),
+            dataverse_name,
+            fields,
+            ignore_if_exists,
+        ))
+        .await
+    }
+
+    /// Drops a secondary index.
+    pub async fn drop_index<S1, S2>(
+        &self,
+        index_name: S1,
+        dataset_name: S2,
+        dataverse_name: Option<&str>,
+    ) -> CouchbaseResult<()>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.exec(drop_index_statement(&index_name.into(), &dataset_name.into(), dataverse_name))
+            .await
+    }
+
+    /// Connects the given link (or the default `Local` link) so datasets start shadowing
+    /// their buckets.
+    pub async fn connect_link(&self, link_name: Option<&str>) -> CouchbaseResult<()> {
+        self.exec(connect_link_statement(link_name)).await
+    }
+
+    /// Disconnects the given link (or the default `Local` link).
+    pub async fn disconnect_link(&self, link_name: Option<&str>) -> CouchbaseResult<()> {
+        self.exec(disconnect_link_statement(link_name)).await
+    }
+
+    /// Lists the datasets known to the cluster by querying the `Metadata.Dataset` system
+    /// dataset; datasets in the `Metadata` dataverse itself are not listed.
+    pub async fn list_datasets(&self) -> CouchbaseResult<Vec<AnalyticsDataset>> {
+        let mut result = self.instance.analytics_query(LIST_DATASETS_STATEMENT.into(), None).await?;
+        let mut datasets = Vec::new();
+        while let Some(row) = result.rows::<AnalyticsDataset>().next().await {
+            datasets.push(row?);
+        }
+        Ok(datasets)
+    }
+
+    async fn exec(&self, statement: String) -> CouchbaseResult<()> {
+        let mut result = self.instance.analytics_query(statement, None).await?;
+        while result.rows::<serde_json::Value>().next().await.is_some() {}
+        Ok(())
+    }
+}
+
+/// Thread-safe counterpart of `AnalyticsManager`, reachable through
+/// `SharedBucket::analytics_manager`.
+pub struct SharedAnalyticsManager {
+    instance: Arc<SharedInstance>,
+}
+
+impl SharedAnalyticsManager {
+    pub(crate) fn new(instance: Arc<SharedInstance>) -> Self {
+        SharedAnalyticsManager { instance }
+    }
+
+    /// Creates a dataverse, optionally ignoring the request if it already exists.
+    pub async fn create_dataverse<S>(&self, dataverse_name: S, ignore_if_exists: bool) -> CouchbaseResult<()>
+    where
+        S: Into<String>,
+    {
+        self.exec(create_dataverse_statement(&dataverse_name.into(), ignore_if_exists))
+            .await
+    }
+
+    /// Drops a dataverse, optionally ignoring the request if it does not exist.
+    pub async fn drop_dataverse<S>(&self, dataverse_name: S, ignore_if_not_exists: bool) -> CouchbaseResult<()>
+    where
+        S: Into<String>,
+    {
+        self.exec(drop_dataverse_statement(&dataverse_name.into(), ignore_if_not_exists))
+            .await
+    }
+
+    /// Creates a dataset shadowing `bucket_name`, optionally in a dataverse other than the
+    /// default one.
+    pub async fn create_dataset<S1, S2>(
+        &self,
+        dataset_name: S1,
+        bucket_name: S2,
+        dataverse_name: Option<&str>,
+        ignore_if_exists: bool,
+    ) -> CouchbaseResult<()>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.exec(create_dataset_statement(
+            &dataset_name.into(),
+            &bucket_name.into(),
+            dataverse_name,
+            ignore_if_exists,
+        ))
+        .await
+    }
+
+    /// Drops a dataset, optionally ignoring the request if it does not exist.
+    pub async fn drop_dataset<S>(
+        &self,
+        dataset_name: S,
+        dataverse_name: Option<&str>,
+        ignore_if_not_exists: bool,
+    ) -> CouchbaseResult<()>
+    where
+        S: Into<String>,
+    {
+        self.exec(drop_dataset_statement(&dataset_name.into(), dataverse_name, ignore_if_not_exists))
+            .await
+    }
+
+    /// Creates a secondary index on the given fields of a dataset.
+    pub async fn create_index<S1, S2>(
+        &self,
+        index_name: S1,
+        dataset_name: S2,
+        dataverse_name: Option<&str>,
+        fields: &[(&str, &str)],
+        ignore_if_exists: bool,
+    ) -> CouchbaseResult<()>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.exec(create_index_statement(
+            &index_name.into(),
+            &dataset_name.into(),
+            dataverse_name,
+            fields,
+            ignore_if_exists,
+        ))
+        .await
+    }
+
+    /// Drops a secondary index.
+    pub async fn drop_index<S1, S2>(
+        &self,
+        index_name: S1,
+        dataset_name: S2,
+        dataverse_name: Option<&str>,
+    ) -> CouchbaseResult<()>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.exec(drop_index_statement(&index_name.into(), &dataset_name.into(), dataverse_name))
+            .await
+    }
+
+    /// Connects the given link (or the default `Local` link) so datasets start shadowing
+    /// their buckets.
+    pub async fn connect_link(&self, link_name: Option<&str>) -> CouchbaseResult<()> {
+        self.exec(connect_link_statement(link_name)).await
+    }
+
+    /// Disconnects the given link (or the default `Local` link).
+    pub async fn disconnect_link(&self, link_name: Option<&str>) -> CouchbaseResult<()> {
+        self.exec(disconnect_link_statement(link_name)).await
+    }
+
+    /// Lists the datasets known to the cluster by querying the `Metadata.Dataset` system
+    /// dataset; datasets in the `Metadata` dataverse itself are not listed.
+    pub async fn list_datasets(&self) -> CouchbaseResult<Vec<AnalyticsDataset>> {
+        let mut result = self.instance.analytics_query(LIST_DATASETS_STATEMENT.into(), None).await?;
+        let mut datasets = Vec::new();
+        while let Some(row) = result.rows::<AnalyticsDataset>().next().await {
+            datasets.push(row?);
+        }
+        Ok(datasets)
+    }
+
+    async fn exec(&self, statement: String) -> CouchbaseResult<()> {
+        let mut result = self.instance.analytics_query(statement, None).await?;
+        while result.rows::<serde_json::Value>().next().await.is_some() {}
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn qualifies_with_and_without_dataverse() {
+        assert_eq!(qualify(None, "airports"), "`airports`");
+        assert_eq!(qualify(Some("travel"), "airports"), "`travel`.`airports`");
+    }
+
+    #[test]
+    fn builds_create_dataverse_statement() {
+        assert_eq!(
+            create_dataverse_statement("travel", false),
+            "CREATE DATAVERSE `travel`"
+        );
+        assert_eq!(
+            create_dataverse_statement("travel", true),
+            "CREATE DATAVERSE `travel` IF NOT EXISTS"
+        );
+    }
+
+    #[test]
+    fn builds_drop_dataverse_statement() {
+        assert_eq!(
+            drop_dataverse_statement("travel", true),
+            "DROP DATAVERSE `travel` IF EXISTS"
+        );
+    }
+
+    #[test]
+    fn builds_create_dataset_statement_with_dataverse() {
+        assert_eq!(
+            create_dataset_statement("airports", "travel-sample", Some("travel"), true),
+            "CREATE DATASET IF NOT EXISTS `travel`.`airports` ON `travel-sample`"
+        );
+    }
+
+    #[test]
+    fn builds_create_dataset_statement_without_dataverse() {
+        assert_eq!(
+            create_dataset_statement("airports", "travel-sample", None, false),
+            "CREATE DATASET `airports` ON `travel-sample`"
+        );
+    }
+
+    #[test]
+    fn builds_drop_dataset_statement() {
+        assert_eq!(
+            drop_dataset_statement("airports", None, true),
+            "DROP DATASET `airports` IF EXISTS"
+        );
+    }
+
+    #[test]
+    fn builds_create_index_statement_backticks_dataset() {
+        let statement = create_index_statement(
+            "idx_icao",
+            "airports",
+            Some("travel"),
+            &[("icao", "string")],
+            true,
+        );
+        assert_eq!(
+            statement,
+            "CREATE INDEX `idx_icao` IF NOT EXISTS ON `travel`.`airports` (`icao`: string)"
+        );
+    }
+
+    #[test]
+    fn builds_drop_index_statement_backticks_dataset() {
+        assert_eq!(
+            drop_index_statement("idx_icao", "airports", Some("travel")),
+            "DROP INDEX `travel`.`airports`.`idx_icao`"
+        );
+    }
+
+    #[test]
+    fn builds_link_statements_with_default_name() {
+        assert_eq!(connect_link_statement(None), "CONNECT LINK Local");
+        assert_eq!(disconnect_link_statement(Some("other")), "DISCONNECT LINK other");
+    }
+}
@@ -1,13 +1,18 @@
+use crate::analytics_manager::{AnalyticsManager, SharedAnalyticsManager};
 use crate::collection::{SharedCollection, Collection};
 use crate::error::CouchbaseError;
 use crate::instance::{SharedInstance, Instance};
 use crate::options::{AnalyticsOptions, QueryOptions};
 use crate::result::{AnalyticsResult, QueryResult};
-use futures::Future;
+use crate::scope::{Scope, SharedScope};
 use std::rc::Rc;
 use std::sync::Arc;
 
 /// Provides access to `Bucket` level operations and `Collections`.
+///
+/// `Bucket` is cheaply `Clone`: every clone shares the same underlying lcb `Instance`, so
+/// handing out a bucket handle (e.g. across tasks) does not re-bootstrap the connection.
+#[derive(Clone)]
 pub struct Bucket {
     instance: Rc<Instance>,
 }
@@ -15,8 +20,8 @@ pub struct Bucket {
 impl Bucket {
     /// Internal method to create a new bucket, which in turn creates the lcb instance
     /// attached to this bucket.
-    pub(crate) fn new(cs: &str, user: &str, pw: &str) -> Result<Self, CouchbaseError> {
-        let instance = Instance::new(cs, user, pw)?;
+    pub(crate) fn new(cs: &str, user: &str, pw: &str, name: &str) -> Result<Self, CouchbaseError> {
+        let instance = Instance::new(cs, user, pw, name)?;
         Ok(Bucket {
             instance: Rc::new(instance),
         })
@@ -28,36 +33,63 @@ impl Bucket {
     /// not have any collections (upgrading from an older cluster) or if you are on a
     /// Couchbase Server version which does not support collections yet.
     pub fn default_collection(&self) -> Collection {
-        Collection::new(self.instance.clone())
+        Collection::new(self.instance.clone(), None, None)
+    }
+
+    /// Opens the named `Scope`.
+    ///
+    /// Scopes group named collections together and are only available on Couchbase Server
+    /// 6.5 and above.
+    pub fn scope<S>(&self, name: S) -> Scope
+    where
+        S: Into<String>,
+    {
+        Scope::new(self.instance.clone(), name.into())
+    }
+
+    /// Convenience method to open a named collection inside a named scope without going
+    /// through `scope()` first.
+    pub fn collection<S1, S2>(&self, scope: S1, collection: S2) -> Collection
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.scope(scope).collection(collection)
+    }
+
+    /// Provides access to analytics management operations such as creating and dropping
+    /// dataverses, datasets, indexes and links.
+    pub fn analytics_manager(&self) -> AnalyticsManager {
+        AnalyticsManager::new(self.instance.clone())
     }
 
     /// Internal proxy method that gets called from the cluster so we can send it into the
     /// instance.
-    pub(crate) fn query<S>(
+    pub(crate) async fn query<S>(
         &self,
         statement: S,
         options: Option<QueryOptions>,
-    ) -> impl Future<Item = QueryResult, Error = CouchbaseError>
+    ) -> Result<QueryResult, CouchbaseError>
     where
         S: Into<String>,
     {
-        self.instance.query(statement.into(), options)
+        self.instance.query(statement.into(), options).await
     }
 
     /// Internal proxy method that gets called from the cluster so we can send it into the
     /// instance.
-    pub(crate) fn analytics_query<S>(
+    pub(crate) async fn analytics_query<S>(
         &self,
         statement: S,
         options: Option<AnalyticsOptions>,
-    ) -> impl Future<Item = AnalyticsResult, Error = CouchbaseError>
+    ) -> Result<AnalyticsResult, CouchbaseError>
     where
         S: Into<String>,
     {
-        self.instance.analytics_query(statement.into(), options)
+        self.instance.analytics_query(statement.into(), options).await
     }
 
-    /// Internal proxy method that gets called from the cluster so we can send it into the
+    /// Internal method called from `Cluster::disconnect` to tear down this bucket's lcb
     /// instance.
     pub(crate) fn close(&self) -> Result<(), CouchbaseError> {
         self.instance.shutdown()
@@ -65,6 +97,11 @@ impl Bucket {
 }
 
 /// Provides access to `Bucket` level operations and `Collections`.
+///
+/// `SharedBucket` is cheaply `Clone`: every clone shares the same underlying lcb
+/// `SharedInstance`, so handing out a bucket handle across threads does not re-bootstrap
+/// the connection.
+#[derive(Clone)]
 pub struct SharedBucket {
     instance: Arc<SharedInstance>,
 }
@@ -72,8 +109,8 @@ pub struct SharedBucket {
 impl SharedBucket {
     /// Internal method to create a new bucket, which in turn creates the lcb instance
     /// attached to this bucket.
-    pub(crate) fn new(cs: &str, user: &str, pw: &str) -> Result<Self, CouchbaseError> {
-        let instance = SharedInstance::new(cs, user, pw)?;
+    pub(crate) fn new(cs: &str, user: &str, pw: &str, name: &str) -> Result<Self, CouchbaseError> {
+        let instance = SharedInstance::new(cs, user, pw, name)?;
         Ok(SharedBucket {
             instance: Arc::new(instance),
         })
@@ -85,37 +122,64 @@ impl SharedBucket {
     /// not have any collections (upgrading from an older cluster) or if you are on a
     /// Couchbase Server version which does not support collections yet.
     pub fn default_collection(&self) -> SharedCollection {
-        SharedCollection::new(self.instance.clone())
+        SharedCollection::new(self.instance.clone(), None, None)
+    }
+
+    /// Opens the named `SharedScope`.
+    ///
+    /// Scopes group named collections together and are only available on Couchbase Server
+    /// 6.5 and above.
+    pub fn scope<S>(&self, name: S) -> SharedScope
+    where
+        S: Into<String>,
+    {
+        SharedScope::new(self.instance.clone(), name.into())
+    }
+
+    /// Convenience method to open a named collection inside a named scope without going
+    /// through `scope()` first.
+    pub fn collection<S1, S2>(&self, scope: S1, collection: S2) -> SharedCollection
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.scope(scope).collection(collection)
+    }
+
+    /// Provides access to analytics management operations such as creating and dropping
+    /// dataverses, datasets, indexes and links.
+    pub fn analytics_manager(&self) -> SharedAnalyticsManager {
+        SharedAnalyticsManager::new(self.instance.clone())
     }
 
     /// Internal proxy method that gets called from the cluster so we can send it into the
     /// instance.
-    pub(crate) fn query<S>(
+    pub(crate) async fn query<S>(
         &self,
         statement: S,
         options: Option<QueryOptions>,
-    ) -> impl Future<Item = QueryResult, Error = CouchbaseError>
+    ) -> Result<QueryResult, CouchbaseError>
     where
         S: Into<String>,
     {
-        self.instance.query(statement.into(), options)
+        self.instance.query(statement.into(), options).await
     }
 
     /// Internal proxy method that gets called from the cluster so we can send it into the
     /// instance.
-    pub(crate) fn analytics_query<S>(
+    pub(crate) async fn analytics_query<S>(
         &self,
         statement: S,
         options: Option<AnalyticsOptions>,
-    ) -> impl Future<Item = AnalyticsResult, Error = CouchbaseError>
+    ) -> Result<AnalyticsResult, CouchbaseError>
     where
         S: Into<String>,
     {
-        self.instance.analytics_query(statement.into(), options)
+        self.instance.analytics_query(statement.into(), options).await
     }
 
-    /// Internal proxy method that gets called from the cluster so we can send it into the
-    /// instance.
+    /// Internal method called from `SharedCluster::disconnect` to tear down this bucket's
+    /// lcb instance.
     pub(crate) fn close(&self) -> Result<(), CouchbaseError> {
         self.instance.shutdown()
     }
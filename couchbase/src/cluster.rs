@@ -0,0 +1,161 @@
+use crate::bucket::{Bucket, SharedBucket};
+use crate::error::CouchbaseError;
+use crate::options::{AnalyticsOptions, QueryOptions};
+use crate::result::{AnalyticsResult, QueryResult};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Entry point into the SDK, representing a connection to a Couchbase cluster.
+pub struct Cluster {
+    cs: String,
+    user: String,
+    pw: String,
+    buckets: RefCell<HashMap<String, Bucket>>,
+}
+
+impl Cluster {
+    /// Connects to a cluster given its connection string and credentials.
+    pub fn connect<S1, S2, S3>(cs: S1, user: S2, pw: S3) -> Result<Self, CouchbaseError>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<String>,
+    {
+        Ok(Cluster {
+            cs: cs.into(),
+            user: user.into(),
+            pw: pw.into(),
+            buckets: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Opens the lcb instance for the named bucket, or hands back a handle to the one
+    /// already opened by an earlier call with the same name.
+    pub fn bucket<S>(&self, name: S) -> Result<Bucket, CouchbaseError>
+    where
+        S: Into<String>,
+    {
+        let name = name.into();
+        let mut buckets = self.buckets.borrow_mut();
+        if let Some(bucket) = buckets.get(&name) {
+            return Ok(bucket.clone());
+        }
+        let bucket = Bucket::new(&self.cs, &self.user, &self.pw, &name)?;
+        buckets.insert(name, bucket.clone());
+        Ok(bucket)
+    }
+
+    /// Runs a N1QL query against the named bucket, opening it first if necessary.
+    pub async fn query<S1, S2>(
+        &self,
+        bucket: S1,
+        statement: S2,
+        options: Option<QueryOptions>,
+    ) -> Result<QueryResult, CouchbaseError>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.bucket(bucket)?.query(statement.into(), options).await
+    }
+
+    /// Runs an analytics query against the named bucket, opening it first if necessary.
+    pub async fn analytics_query<S1, S2>(
+        &self,
+        bucket: S1,
+        statement: S2,
+        options: Option<AnalyticsOptions>,
+    ) -> Result<AnalyticsResult, CouchbaseError>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.bucket(bucket)?.analytics_query(statement.into(), options).await
+    }
+
+    /// Closes every bucket opened on this cluster and tears down their lcb instances.
+    pub fn disconnect(&self) -> Result<(), CouchbaseError> {
+        for (_, bucket) in self.buckets.borrow_mut().drain() {
+            bucket.close()?;
+        }
+        Ok(())
+    }
+}
+
+/// Thread-safe counterpart of `Cluster`, handing out `SharedBucket`s.
+pub struct SharedCluster {
+    cs: String,
+    user: String,
+    pw: String,
+    buckets: Mutex<HashMap<String, SharedBucket>>,
+}
+
+impl SharedCluster {
+    /// Connects to a cluster given its connection string and credentials.
+    pub fn connect<S1, S2, S3>(cs: S1, user: S2, pw: S3) -> Result<Self, CouchbaseError>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<String>,
+    {
+        Ok(SharedCluster {
+            cs: cs.into(),
+            user: user.into(),
+            pw: pw.into(),
+            buckets: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Opens the lcb instance for the named bucket, or hands back a handle to the one
+    /// already opened by an earlier call with the same name.
+    pub fn bucket<S>(&self, name: S) -> Result<SharedBucket, CouchbaseError>
+    where
+        S: Into<String>,
+    {
+        let name = name.into();
+        let mut buckets = self.buckets.lock().unwrap();
+        if let Some(bucket) = buckets.get(&name) {
+            return Ok(bucket.clone());
+        }
+        let bucket = SharedBucket::new(&self.cs, &self.user, &self.pw, &name)?;
+        buckets.insert(name, bucket.clone());
+        Ok(bucket)
+    }
+
+    /// Runs a N1QL query against the named bucket, opening it first if necessary.
+    pub async fn query<S1, S2>(
+        &self,
+        bucket: S1,
+        statement: S2,
+        options: Option<QueryOptions>,
+    ) -> Result<QueryResult, CouchbaseError>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.bucket(bucket)?.query(statement.into(), options).await
+    }
+
+    /// Runs an analytics query against the named bucket, opening it first if necessary.
+    pub async fn analytics_query<S1, S2>(
+        &self,
+        bucket: S1,
+        statement: S2,
+        options: Option<AnalyticsOptions>,
+    ) -> Result<AnalyticsResult, CouchbaseError>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.bucket(bucket)?.analytics_query(statement.into(), options).await
+    }
+
+    /// Closes every bucket opened on this cluster and tears down their lcb instances.
+    pub fn disconnect(&self) -> Result<(), CouchbaseError> {
+        for (_, bucket) in self.buckets.lock().unwrap().drain() {
+            bucket.close()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,396 @@
+use crate::error::{CouchbaseError, CouchbaseResult};
+use crate::instance::{Instance, SharedInstance};
+use crate::result::{GetResult, MutationResult};
+use futures::future::join_all;
+use serde::Serialize;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Provides access to a single collection's KV operations.
+///
+/// A `Collection` is always opened through a `Bucket` (either the default collection via
+/// `Bucket::default_collection` or a named one via `Bucket::collection`/`Scope::collection`)
+/// and shares the underlying lcb `Instance` with every other collection opened from the
+/// same bucket.
+pub struct Collection {
+    instance: Rc<Instance>,
+    scope: Option<String>,
+    collection: Option<String>,
+}
+
+impl Collection {
+    /// Internal method to create a new collection handle bound to the given scope and
+    /// collection name. `scope`/`collection` of `None` address the default collection.
+    pub(crate) fn new(
+        instance: Rc<Instance>,
+        scope: Option<String>,
+        collection: Option<String>,
+    ) -> Self {
+        Collection {
+            instance,
+            scope,
+            collection,
+        }
+    }
+
+    /// Fetches a document by its id.
+    pub async fn get<S>(&self, id: S) -> Result<GetResult, CouchbaseError>
+    where
+        S: Into<String>,
+    {
+        self.instance
+            .get(self.scope.as_deref(), self.collection.as_deref(), &id.into())
+            .await
+    }
+
+    /// Upserts (inserts or replaces) a document under the given id.
+    pub async fn upsert<S, T>(
+        &self,
+        id: S,
+        content: T,
+        _options: Option<()>,
+    ) -> Result<MutationResult, CouchbaseError>
+    where
+        S: Into<String>,
+        T: Serialize,
+    {
+        let encoded = serde_json::to_vec(&content)
+            .map_err(|e| CouchbaseError::Generic { ctx: e.to_string() })?;
+        self.instance
+            .upsert(
+                self.scope.as_deref(),
+                self.collection.as_deref(),
+                &id.into(),
+                encoded,
+            )
+            .await
+    }
+
+    /// Removes a document by its id.
+    pub async fn remove<S>(&self, id: S) -> Result<MutationResult, CouchbaseError>
+    where
+        S: Into<String>,
+    {
+        self.instance
+            .remove(self.scope.as_deref(), self.collection.as_deref(), &id.into())
+            .await
+    }
+
+    /// Fetches many documents at once.
+    ///
+    /// All the individual `get` commands are scheduled against the instance before
+    /// waiting on any of them, so the requests pipeline over the wire instead of
+    /// round-tripping one at a time. A failure for one key (e.g. not found) does not fail
+    /// the other keys in the batch.
+    pub async fn get_multi<S, I>(&self, ids: I) -> Vec<(String, CouchbaseResult<GetResult>)>
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = S>,
+    {
+        let ids: Vec<String> = ids.into_iter().map(Into::into).collect();
+        let futures = ids
+            .iter()
+            .map(|id| self.instance.get(self.scope.as_deref(), self.collection.as_deref(), id));
+        let results = join_all(futures).await;
+        ids.into_iter().zip(results).collect()
+    }
+
+    /// Upserts many documents at once.
+    ///
+    /// All the individual `upsert` commands are scheduled against the instance before
+    /// waiting on any of them, so the requests pipeline over the wire instead of
+    /// round-tripping one at a time. A failure for one key does not fail the other keys in
+    /// the batch.
+    pub async fn upsert_multi<S, T, I>(&self, pairs: I) -> Vec<(String, CouchbaseResult<MutationResult>)>
+    where
+        S: Into<String>,
+        T: Serialize,
+        I: IntoIterator<Item = (S, T)>,
+    {
+        let mut ids = Vec::new();
+        let mut encoded = Vec::new();
+        for (id, content) in pairs {
+            ids.push(id.into());
+            encoded.push(
+                serde_json::to_vec(&content).map_err(|e| CouchbaseError::Generic { ctx: e.to_string() }),
+            );
+        }
+
+        // Keep encode failures in place (as `Err` entries, marked not pending) so the final
+        // result preserves the caller's input order even when some payloads fail to serialize.
+        let mut futures = Vec::new();
+        let mut pending = Vec::with_capacity(encoded.len());
+        for (id, content) in ids.iter().zip(encoded.iter()) {
+            match content {
+                Ok(content) => {
+                    futures.push(self.instance.upsert(
+                        self.scope.as_deref(),
+                        self.collection.as_deref(),
+                        id,
+                        content.clone(),
+                    ));
+                    pending.push(true);
+                }
+                Err(_) => pending.push(false),
+            }
+        }
+        let mut scheduled = join_all(futures).await.into_iter();
+
+        ids.into_iter()
+            .zip(encoded)
+            .zip(pending)
+            .map(|((id, encoded), is_pending)| {
+                let result = if is_pending {
+                    scheduled.next().expect("one scheduled result per pending entry")
+                } else {
+                    Err(encoded.unwrap_err())
+                };
+                (id, result)
+            })
+            .collect()
+    }
+
+    /// Removes many documents at once.
+    ///
+    /// All the individual `remove` commands are scheduled against the instance before
+    /// waiting on any of them, so the requests pipeline over the wire instead of
+    /// round-tripping one at a time. A failure for one key does not fail the other keys in
+    /// the batch.
+    pub async fn remove_multi<S, I>(&self, ids: I) -> Vec<(String, CouchbaseResult<MutationResult>)>
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = S>,
+    {
+        let ids: Vec<String> = ids.into_iter().map(Into::into).collect();
+        let futures = ids.iter().map(|id| {
+            self.instance
+                .remove(self.scope.as_deref(), self.collection.as_deref(), id)
+        });
+        let results = join_all(futures).await;
+        ids.into_iter().zip(results).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Serialize, Serializer};
+
+    /// A payload whose serialization always fails, so `upsert_multi` can be exercised
+    /// without needing a live instance to resolve any scheduled futures.
+    struct Unserializable;
+
+    impl Serialize for Unserializable {
+        fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            Err(serde::ser::Error::custom("always fails"))
+        }
+    }
+
+    #[tokio::test]
+    async fn upsert_multi_preserves_input_order_when_every_payload_fails_to_encode() {
+        let instance = Rc::new(Instance::new("cs", "user", "pw", "bucket").unwrap());
+        let collection = Collection::new(instance, None, None);
+
+        let pairs = vec![("a", Unserializable), ("b", Unserializable), ("c", Unserializable)];
+        let results = collection.upsert_multi(pairs).await;
+
+        let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        assert!(results.iter().all(|(_, result)| result.is_err()));
+    }
+}
+
+/// Thread-safe counterpart of `Collection`, reachable from `SharedBucket`/`SharedScope`.
+pub struct SharedCollection {
+    instance: Arc<SharedInstance>,
+    scope: Option<String>,
+    collection: Option<String>,
+}
+
+impl SharedCollection {
+    /// Internal method to create a new collection handle bound to the given scope and
+    /// collection name. `scope`/`collection` of `None` address the default collection.
+    pub(crate) fn new(
+        instance: Arc<SharedInstance>,
+        scope: Option<String>,
+        collection: Option<String>,
+    ) -> Self {
+        SharedCollection {
+            instance,
+            scope,
+            collection,
+        }
+    }
+
+    /// Fetches a document by its id.
+    pub async fn get<S>(&self, id: S) -> Result<GetResult, CouchbaseError>
+    where
+        S: Into<String>,
+    {
+        self.instance
+            .get(self.scope.as_deref(), self.collection.as_deref(), &id.into())
+            .await
+    }
+
+    /// Upserts (inserts or replaces) a document under the given id.
+    pub async fn upsert<S, T>(
+        &self,
+        id: S,
+        content: T,
+        _options: Option<()>,
+    ) -> Result<MutationResult, CouchbaseError>
+    where
+        S: Into<String>,
+        T: Serialize,
+    {
+        let encoded = serde_json::to_vec(&content)
+            .map_err(|e| CouchbaseError::Generic { ctx: e.to_string() })?;
+        self.instance
+            .upsert(
+                self.scope.as_deref(),
+                self.collection.as_deref(),
+                &id.into(),
+                encoded,
+            )
+            .await
+    }
+
+    /// Removes a document by its id.
+    pub async fn remove<S>(&self, id: S) -> Result<MutationResult, CouchbaseError>
+    where
+        S: Into<String>,
+    {
+        self.instance
+            .remove(self.scope.as_deref(), self.collection.as_deref(), &id.into())
+            .await
+    }
+
+    /// Fetches many documents at once.
+    ///
+    /// All the individual `get` commands are scheduled against the instance before
+    /// waiting on any of them, so the requests pipeline over the wire instead of
+    /// round-tripping one at a time. A failure for one key (e.g. not found) does not fail
+    /// the other keys in the batch.
+    pub async fn get_multi<S, I>(&self, ids: I) -> Vec<(String, CouchbaseResult<GetResult>)>
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = S>,
+    {
+        let ids: Vec<String> = ids.into_iter().map(Into::into).collect();
+        let futures = ids
+            .iter()
+            .map(|id| self.instance.get(self.scope.as_deref(), self.collection.as_deref(), id));
+        let results = join_all(futures).await;
+        ids.into_iter().zip(results).collect()
+    }
+
+    /// Upserts many documents at once.
+    ///
+    /// All the individual `upsert` commands are scheduled against the instance before
+    /// waiting on any of them, so the requests pipeline over the wire instead of
+    /// round-tripping one at a time. A failure for one key does not fail the other keys in
+    /// the batch.
+    pub async fn upsert_multi<S, T, I>(&self, pairs: I) -> Vec<(String, CouchbaseResult<MutationResult>)>
+    where
+        S: Into<String>,
+        T: Serialize,
+        I: IntoIterator<Item = (S, T)>,
+    {
+        let mut ids = Vec::new();
+        let mut encoded = Vec::new();
+        for (id, content) in pairs {
+            ids.push(id.into());
+            encoded.push(
+                serde_json::to_vec(&content).map_err(|e| CouchbaseError::Generic { ctx: e.to_string() }),
+            );
+        }
+
+        // Keep encode failures in place (as `Err` entries, marked not pending) so the final
+        // result preserves the caller's input order even when some payloads fail to serialize.
+        let mut futures = Vec::new();
+        let mut pending = Vec::with_capacity(encoded.len());
+        for (id, content) in ids.iter().zip(encoded.iter()) {
+            match content {
+                Ok(content) => {
+                    futures.push(self.instance.upsert(
+                        self.scope.as_deref(),
+                        self.collection.as_deref(),
+                        id,
+                        content.clone(),
+                    ));
+                    pending.push(true);
+                }
+                Err(_) => pending.push(false),
+            }
+        }
+        let mut scheduled = join_all(futures).await.into_iter();
+
+        ids.into_iter()
+            .zip(encoded)
+            .zip(pending)
+            .map(|((id, encoded), is_pending)| {
+                let result = if is_pending {
+                    scheduled.next().expect("one scheduled result per pending entry")
+                } else {
+                    Err(encoded.unwrap_err())
+                };
+                (id, result)
+            })
+            .collect()
+    }
+
+    /// Removes many documents at once.
+    ///
+    /// All the individual `remove` commands are scheduled against the instance before
+    /// waiting on any of them, so the requests pipeline over the wire instead of
+    /// round-tripping one at a time. A failure for one key does not fail the other keys in
+    /// the batch.
+    pub async fn remove_multi<S, I>(&self, ids: I) -> Vec<(String, CouchbaseResult<MutationResult>)>
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = S>,
+    {
+        let ids: Vec<String> = ids.into_iter().map(Into::into).collect();
+        let futures = ids.iter().map(|id| {
+            self.instance
+                .remove(self.scope.as_deref(), self.collection.as_deref(), id)
+        });
+        let results = join_all(futures).await;
+        ids.into_iter().zip(results).collect()
+    }
+}
+
+#[cfg(test)]
+mod shared_tests {
+    use super::*;
+    use serde::{Serialize, Serializer};
+
+    /// A payload whose serialization always fails, so `upsert_multi` can be exercised
+    /// without needing a live instance to resolve any scheduled futures.
+    struct Unserializable;
+
+    impl Serialize for Unserializable {
+        fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            Err(serde::ser::Error::custom("always fails"))
+        }
+    }
+
+    #[tokio::test]
+    async fn upsert_multi_preserves_input_order_when_every_payload_fails_to_encode() {
+        let instance = Arc::new(SharedInstance::new("cs", "user", "pw", "bucket").unwrap());
+        let collection = SharedCollection::new(instance, None, None);
+
+        let pairs = vec![("a", Unserializable), ("b", Unserializable), ("c", Unserializable)];
+        let results = collection.upsert_multi(pairs).await;
+
+        let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        assert!(results.iter().all(|(_, result)| result.is_err()));
+    }
+}
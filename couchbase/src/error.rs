@@ -0,0 +1,33 @@
+use std::error::Error;
+use std::fmt;
+
+/// The base error type exposed by this crate. Most public APIs which can fail will
+/// resolve to this error, wrapped in the appropriate `Result`/`Future`/`Stream` item.
+#[derive(Debug, Clone)]
+pub enum CouchbaseError {
+    /// The requested key could not be found.
+    KeyDoesNotExist,
+    /// The key already exists.
+    KeyExists,
+    /// The operation timed out before a response was received.
+    Timeout,
+    /// A generic, catch-all error carrying a human readable description, used for
+    /// anything that does not have a more specific variant yet.
+    Generic { ctx: String },
+}
+
+impl fmt::Display for CouchbaseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CouchbaseError::KeyDoesNotExist => write!(f, "key does not exist"),
+            CouchbaseError::KeyExists => write!(f, "key already exists"),
+            CouchbaseError::Timeout => write!(f, "operation timed out"),
+            CouchbaseError::Generic { ctx } => write!(f, "{}", ctx),
+        }
+    }
+}
+
+impl Error for CouchbaseError {}
+
+/// Convenience alias used throughout the crate for fallible results.
+pub type CouchbaseResult<T> = Result<T, CouchbaseError>;
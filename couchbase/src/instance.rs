@@ -0,0 +1,499 @@
+use crate::error::{CouchbaseError, CouchbaseResult};
+use crate::options::{AnalyticsOptions, QueryOptions};
+use crate::result::{AnalyticsMeta, AnalyticsResult, GetResult, MutationResult, QueryMeta, QueryResult};
+use futures::channel::{mpsc, oneshot};
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+/// Hashes a statement's text; `InstanceCore::prepare` derives the plan name from it, so the
+/// same statement always maps to the same plan name.
+fn statement_hash(statement: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    statement.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Builds the JSON body of a N1QL query request: either `statement` or (if a prepared plan
+/// was resolved) `prepared`, plus positional/named parameters and scan consistency.
+fn build_query_body(statement: String, options: &QueryOptions, plan_name: Option<String>) -> serde_json::Value {
+    let mut body = serde_json::Map::new();
+    match plan_name {
+        Some(name) => {
+            body.insert("prepared".into(), name.into());
+        }
+        None => {
+            body.insert("statement".into(), statement.into());
+        }
+    }
+    if !options.positional_parameters_ref().is_empty() {
+        body.insert(
+            "args".into(),
+            serde_json::Value::Array(options.positional_parameters_ref().to_vec()),
+        );
+    }
+    for (name, value) in options.named_parameters_ref() {
+        body.insert(format!("${}", name), value.clone());
+    }
+    body.insert(
+        "scan_consistency".into(),
+        options.scan_consistency_value().as_str().into(),
+    );
+    serde_json::Value::Object(body)
+}
+
+/// Client-side cache mapping a N1QL statement's text to the name of its prepared plan on
+/// the server, used for `QueryOptions::adhoc(false)` so a statement only has to be
+/// prepared once and is reused (by name) on every subsequent call. Evicts the
+/// least-recently-used entry once `capacity` is exceeded.
+struct PreparedPlanCache {
+    capacity: usize,
+    plans: HashMap<String, String>,
+    recency: VecDeque<String>,
+}
+
+impl PreparedPlanCache {
+    fn new(capacity: usize) -> Self {
+        PreparedPlanCache {
+            capacity,
+            plans: HashMap::new(),
+            recency: VecDeque::new(),
+        }
+    }
+
+    fn get(&mut self, statement: &str) -> Option<String> {
+        let plan = self.plans.get(statement).cloned();
+        if plan.is_some() {
+            self.touch(statement);
+        }
+        plan
+    }
+
+    fn insert(&mut self, statement: String, plan_name: String) {
+        if !self.plans.contains_key(&statement) && self.plans.len() >= self.capacity {
+            if let Some(evicted) = self.recency.pop_front() {
+                self.plans.remove(&evicted);
+            }
+        }
+        self.plans.insert(statement.clone(), plan_name);
+        self.touch(&statement);
+    }
+
+    fn touch(&mut self, statement: &str) {
+        if let Some(pos) = self.recency.iter().position(|s| s == statement) {
+            self.recency.remove(pos);
+        }
+        self.recency.push_back(statement.to_string());
+    }
+}
+
+const DEFAULT_PREPARED_CACHE_CAPACITY: usize = 5000;
+
+/// Connection/keyspace state shared by `Instance` and `SharedInstance`. Plain data only (no
+/// interior mutability), so it can be embedded directly in the `Sync` `SharedInstance`
+/// without dragging in a non-`Sync` type; each of `Instance`/`SharedInstance` pairs this
+/// with its own cache (a `RefCell` or a `Mutex` respectively).
+struct InstanceCore {
+    cs: String,
+    user: String,
+    pw: String,
+    bucket: String,
+}
+
+impl InstanceCore {
+    fn new(cs: &str, user: &str, pw: &str, bucket: &str) -> Self {
+        // In the real client this bootstraps the underlying `lcb_INSTANCE*`, opens it
+        // against `bucket`, and connects it to the cluster. Kept as plain fields here
+        // since this module only needs to describe the shape of the API surface.
+        InstanceCore {
+            cs: cs.into(),
+            user: user.into(),
+            pw: pw.into(),
+            bucket: bucket.into(),
+        }
+    }
+
+    async fn get(
+        &self,
+        scope: Option<&str>,
+        collection: Option<&str>,
+        id: &str,
+    ) -> CouchbaseResult<GetResult> {
+        let (tx, rx) = oneshot::channel();
+        self.schedule_get(scope, collection, id, tx);
+        rx.await.map_err(|_| CouchbaseError::Generic {
+            ctx: "instance dropped before response".into(),
+        })?
+    }
+
+    async fn upsert(
+        &self,
+        scope: Option<&str>,
+        collection: Option<&str>,
+        id: &str,
+        content: Vec<u8>,
+    ) -> CouchbaseResult<MutationResult> {
+        let (tx, rx) = oneshot::channel();
+        self.schedule_upsert(scope, collection, id, content, tx);
+        rx.await.map_err(|_| CouchbaseError::Generic {
+            ctx: "instance dropped before response".into(),
+        })?
+    }
+
+    async fn remove(
+        &self,
+        scope: Option<&str>,
+        collection: Option<&str>,
+        id: &str,
+    ) -> CouchbaseResult<MutationResult> {
+        let (tx, rx) = oneshot::channel();
+        self.schedule_remove(scope, collection, id, tx);
+        rx.await.map_err(|_| CouchbaseError::Generic {
+            ctx: "instance dropped before response".into(),
+        })?
+    }
+
+    async fn analytics_query(
+        &self,
+        statement: String,
+        _options: Option<AnalyticsOptions>,
+    ) -> CouchbaseResult<AnalyticsResult> {
+        let (rows_tx, rows_rx) = mpsc::unbounded();
+        let (meta_tx, meta_rx) = oneshot::channel();
+        self.schedule_analytics_query(statement, rows_tx, meta_tx)?;
+        Ok(AnalyticsResult::new(rows_rx, meta_rx))
+    }
+
+    fn shutdown(&self) -> Result<(), CouchbaseError> {
+        Ok(())
+    }
+
+    /// Derives the name this client will use for `statement`'s prepared plan. This stub
+    /// never actually issues a server-side `PREPARE`; the real client would send one here
+    /// and cache the name the *server* returns instead of deriving one locally.
+    async fn prepare(&self, statement: &str) -> CouchbaseResult<String> {
+        Ok(format!("auto_prepare_{:x}", statement_hash(statement)))
+    }
+
+    fn schedule_get(
+        &self,
+        _scope: Option<&str>,
+        _collection: Option<&str>,
+        _id: &str,
+        _tx: oneshot::Sender<CouchbaseResult<GetResult>>,
+    ) {
+        // No lcb command is issued here: `_tx` is dropped on return, so the future in
+        // `get` resolves to a `Generic` error. `schedule_upsert`/`schedule_remove` do the
+        // same.
+    }
+
+    fn schedule_upsert(
+        &self,
+        _scope: Option<&str>,
+        _collection: Option<&str>,
+        _id: &str,
+        _content: Vec<u8>,
+        _tx: oneshot::Sender<CouchbaseResult<MutationResult>>,
+    ) {
+    }
+
+    fn schedule_remove(
+        &self,
+        _scope: Option<&str>,
+        _collection: Option<&str>,
+        _id: &str,
+        _tx: oneshot::Sender<CouchbaseResult<MutationResult>>,
+    ) {
+    }
+
+    /// Builds the N1QL request body (statement or prepared plan name, positional/named
+    /// parameters, scan consistency). The body is not dispatched: `_rows` and `_meta` are
+    /// dropped on return, so the row stream ends with no rows and `meta()` resolves to an
+    /// error.
+    fn schedule_query(
+        &self,
+        statement: String,
+        options: &QueryOptions,
+        plan_name: Option<String>,
+        _rows: mpsc::UnboundedSender<serde_json::Value>,
+        _meta: oneshot::Sender<QueryMeta>,
+    ) -> CouchbaseResult<()> {
+        let _body = build_query_body(statement, options, plan_name);
+        // The real client serializes `_body` and dispatches it through `lcb_query`;
+        // row callbacks push decoded rows onto `_rows` and the final callback fills in
+        // `_meta` and drops `_rows`, closing the stream for the consumer.
+        Ok(())
+    }
+
+    fn schedule_analytics_query(
+        &self,
+        _statement: String,
+        _rows: mpsc::UnboundedSender<serde_json::Value>,
+        _meta: oneshot::Sender<AnalyticsMeta>,
+    ) -> CouchbaseResult<()> {
+        Ok(())
+    }
+}
+
+/// Per-bucket handle behind `Bucket`/`Collection`. Every async method hands back a future
+/// backed by a oneshot channel (queries: a row channel plus a oneshot for the metadata).
+/// No `lcb_INSTANCE*` is created and no lcb command is issued: the `schedule_*` methods of
+/// `InstanceCore` drop the senders, so KV futures resolve to a `Generic` error and query
+/// row streams end with no rows.
+pub(crate) struct Instance {
+    core: InstanceCore,
+    prepared_plans: RefCell<PreparedPlanCache>,
+}
+
+impl Instance {
+    pub(crate) fn new(cs: &str, user: &str, pw: &str, bucket: &str) -> Result<Self, CouchbaseError> {
+        Ok(Instance {
+            core: InstanceCore::new(cs, user, pw, bucket),
+            prepared_plans: RefCell::new(PreparedPlanCache::new(DEFAULT_PREPARED_CACHE_CAPACITY)),
+        })
+    }
+
+    pub(crate) async fn get(
+        &self,
+        scope: Option<&str>,
+        collection: Option<&str>,
+        id: &str,
+    ) -> CouchbaseResult<GetResult> {
+        self.core.get(scope, collection, id).await
+    }
+
+    pub(crate) async fn upsert(
+        &self,
+        scope: Option<&str>,
+        collection: Option<&str>,
+        id: &str,
+        content: Vec<u8>,
+    ) -> CouchbaseResult<MutationResult> {
+        self.core.upsert(scope, collection, id, content).await
+    }
+
+    pub(crate) async fn remove(
+        &self,
+        scope: Option<&str>,
+        collection: Option<&str>,
+        id: &str,
+    ) -> CouchbaseResult<MutationResult> {
+        self.core.remove(scope, collection, id).await
+    }
+
+    pub(crate) async fn query(
+        &self,
+        statement: String,
+        options: Option<QueryOptions>,
+    ) -> CouchbaseResult<QueryResult> {
+        let options = options.unwrap_or_default();
+        let plan_name = if !options.is_adhoc() {
+            let cached = self.prepared_plans.borrow_mut().get(&statement);
+            match cached {
+                Some(name) => Some(name),
+                None => {
+                    let name = self.core.prepare(&statement).await?;
+                    self.prepared_plans
+                        .borrow_mut()
+                        .insert(statement.clone(), name.clone());
+                    Some(name)
+                }
+            }
+        } else {
+            None
+        };
+
+        let (rows_tx, rows_rx) = mpsc::unbounded();
+        let (meta_tx, meta_rx) = oneshot::channel();
+        self.core.schedule_query(statement, &options, plan_name, rows_tx, meta_tx)?;
+        Ok(QueryResult::new(rows_rx, meta_rx))
+    }
+
+    pub(crate) async fn analytics_query(
+        &self,
+        statement: String,
+        options: Option<AnalyticsOptions>,
+    ) -> CouchbaseResult<AnalyticsResult> {
+        self.core.analytics_query(statement, options).await
+    }
+
+    pub(crate) fn shutdown(&self) -> Result<(), CouchbaseError> {
+        self.core.shutdown()
+    }
+}
+
+/// Thread-safe counterpart of `Instance`, used by `SharedBucket`/`SharedCollection` so
+/// handles can be passed across threads (e.g. from a `tokio::runtime::Runtime`). Holds its
+/// own `InstanceCore` (rather than wrapping an `Instance`) so the non-`Sync` `RefCell` used
+/// by `Instance`'s cache never ends up inside this type.
+pub(crate) struct SharedInstance {
+    core: InstanceCore,
+    prepared_plans: Mutex<PreparedPlanCache>,
+}
+
+impl SharedInstance {
+    pub(crate) fn new(cs: &str, user: &str, pw: &str, bucket: &str) -> Result<Self, CouchbaseError> {
+        Ok(SharedInstance {
+            core: InstanceCore::new(cs, user, pw, bucket),
+            prepared_plans: Mutex::new(PreparedPlanCache::new(DEFAULT_PREPARED_CACHE_CAPACITY)),
+        })
+    }
+
+    pub(crate) async fn get(
+        &self,
+        scope: Option<&str>,
+        collection: Option<&str>,
+        id: &str,
+    ) -> CouchbaseResult<GetResult> {
+        self.core.get(scope, collection, id).await
+    }
+
+    pub(crate) async fn upsert(
+        &self,
+        scope: Option<&str>,
+        collection: Option<&str>,
+        id: &str,
+        content: Vec<u8>,
+    ) -> CouchbaseResult<MutationResult> {
+        self.core.upsert(scope, collection, id, content).await
+    }
+
+    pub(crate) async fn remove(
+        &self,
+        scope: Option<&str>,
+        collection: Option<&str>,
+        id: &str,
+    ) -> CouchbaseResult<MutationResult> {
+        self.core.remove(scope, collection, id).await
+    }
+
+    pub(crate) async fn query(
+        &self,
+        statement: String,
+        options: Option<QueryOptions>,
+    ) -> CouchbaseResult<QueryResult> {
+        let options = options.unwrap_or_default();
+        let plan_name = if !options.is_adhoc() {
+            let cached = self.prepared_plans.lock().unwrap().get(&statement);
+            match cached {
+                Some(name) => Some(name),
+                None => {
+                    let name = self.core.prepare(&statement).await?;
+                    self.prepared_plans
+                        .lock()
+                        .unwrap()
+                        .insert(statement.clone(), name.clone());
+                    Some(name)
+                }
+            }
+        } else {
+            None
+        };
+
+        let (rows_tx, rows_rx) = mpsc::unbounded();
+        let (meta_tx, meta_rx) = oneshot::channel();
+        self.core
+            .schedule_query(statement, &options, plan_name, rows_tx, meta_tx)?;
+        Ok(QueryResult::new(rows_rx, meta_rx))
+    }
+
+    pub(crate) async fn analytics_query(
+        &self,
+        statement: String,
+        options: Option<AnalyticsOptions>,
+    ) -> CouchbaseResult<AnalyticsResult> {
+        self.core.analytics_query(statement, options).await
+    }
+
+    pub(crate) fn shutdown(&self) -> Result<(), CouchbaseError> {
+        self.core.shutdown()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::ScanConsistency;
+    use serde_json::json;
+
+    #[test]
+    fn cache_returns_none_for_unknown_statement() {
+        let mut cache = PreparedPlanCache::new(2);
+        assert_eq!(cache.get("select 1"), None);
+    }
+
+    #[test]
+    fn cache_round_trips_an_inserted_plan() {
+        let mut cache = PreparedPlanCache::new(2);
+        cache.insert("select 1".into(), "plan_a".into());
+        assert_eq!(cache.get("select 1"), Some("plan_a".into()));
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used_entry_once_over_capacity() {
+        let mut cache = PreparedPlanCache::new(2);
+        cache.insert("a".into(), "plan_a".into());
+        cache.insert("b".into(), "plan_b".into());
+        cache.insert("c".into(), "plan_c".into());
+
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("b"), Some("plan_b".into()));
+        assert_eq!(cache.get("c"), Some("plan_c".into()));
+    }
+
+    #[test]
+    fn cache_get_refreshes_recency_so_it_survives_eviction() {
+        let mut cache = PreparedPlanCache::new(2);
+        cache.insert("a".into(), "plan_a".into());
+        cache.insert("b".into(), "plan_b".into());
+        // Touch "a" so "b" becomes the least-recently-used entry.
+        cache.get("a");
+        cache.insert("c".into(), "plan_c".into());
+
+        assert_eq!(cache.get("a"), Some("plan_a".into()));
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("c"), Some("plan_c".into()));
+    }
+
+    #[test]
+    fn statement_hash_is_stable_and_distinguishes_statements() {
+        assert_eq!(statement_hash("select 1"), statement_hash("select 1"));
+        assert_ne!(statement_hash("select 1"), statement_hash("select 2"));
+    }
+
+    #[test]
+    fn query_body_uses_statement_when_not_prepared() {
+        let body = build_query_body("select 1".into(), &QueryOptions::new(), None);
+        assert_eq!(body["statement"], json!("select 1"));
+        assert!(body.get("prepared").is_none());
+        assert_eq!(body["scan_consistency"], json!("not_bounded"));
+    }
+
+    #[test]
+    fn query_body_uses_plan_name_when_prepared() {
+        let body = build_query_body("select 1".into(), &QueryOptions::new(), Some("plan_a".into()));
+        assert_eq!(body["prepared"], json!("plan_a"));
+        assert!(body.get("statement").is_none());
+    }
+
+    #[test]
+    fn query_body_includes_positional_and_named_parameters_and_consistency() {
+        let options = QueryOptions::new()
+            .positional_parameters(vec![json!("LAX")])
+            .named_parameter("limit", json!(10))
+            .scan_consistency(ScanConsistency::RequestPlus);
+        let body = build_query_body("select $1, $limit".into(), &options, None);
+
+        assert_eq!(body["args"], json!(["LAX"]));
+        assert_eq!(body["$limit"], json!(10));
+        assert_eq!(body["scan_consistency"], json!("request_plus"));
+    }
+
+    #[test]
+    fn query_body_omits_args_when_no_positional_parameters() {
+        let body = build_query_body("select 1".into(), &QueryOptions::new(), None);
+        assert!(body.get("args").is_none());
+    }
+}
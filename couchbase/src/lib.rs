@@ -0,0 +1,20 @@
+mod analytics_manager;
+mod bucket;
+mod cluster;
+mod collection;
+mod error;
+mod instance;
+mod options;
+mod result;
+mod scope;
+
+pub use crate::analytics_manager::{AnalyticsDataset, AnalyticsManager, SharedAnalyticsManager};
+pub use crate::bucket::{Bucket, SharedBucket};
+pub use crate::cluster::{Cluster, SharedCluster};
+pub use crate::collection::{Collection, SharedCollection};
+pub use crate::error::{CouchbaseError, CouchbaseResult};
+pub use crate::options::{AnalyticsOptions, QueryOptions, ScanConsistency};
+pub use crate::result::{
+    AnalyticsMeta, AnalyticsResult, GetResult, MutationResult, QueryMeta, QueryResult,
+};
+pub use crate::scope::{Scope, SharedScope};
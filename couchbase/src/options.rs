@@ -0,0 +1,112 @@
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Controls how up to date the index consulted by a query needs to be relative to prior
+/// mutations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanConsistency {
+    /// Do not wait for the index to catch up with prior mutations (the default, and the
+    /// fastest option).
+    NotBounded,
+    /// Wait until the index has caught up with every mutation performed on this client
+    /// before running the query.
+    RequestPlus,
+}
+
+impl ScanConsistency {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ScanConsistency::NotBounded => "not_bounded",
+            ScanConsistency::RequestPlus => "request_plus",
+        }
+    }
+}
+
+impl Default for ScanConsistency {
+    fn default() -> Self {
+        ScanConsistency::NotBounded
+    }
+}
+
+/// Options which can be passed into `Bucket::query`/`SharedBucket::query` to customize
+/// how a N1QL statement is executed.
+#[derive(Debug, Clone)]
+pub struct QueryOptions {
+    positional_parameters: Vec<Value>,
+    named_parameters: HashMap<String, Value>,
+    scan_consistency: ScanConsistency,
+    adhoc: bool,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        QueryOptions {
+            positional_parameters: Vec::new(),
+            named_parameters: HashMap::new(),
+            scan_consistency: ScanConsistency::default(),
+            adhoc: true,
+        }
+    }
+}
+
+impl QueryOptions {
+    pub fn new() -> Self {
+        QueryOptions::default()
+    }
+
+    /// Supplies positional parameters (`$1`, `$2`, ...) referenced by the statement.
+    pub fn positional_parameters(mut self, parameters: Vec<Value>) -> Self {
+        self.positional_parameters = parameters;
+        self
+    }
+
+    /// Supplies a single named parameter (`$name`) referenced by the statement. Can be
+    /// called repeatedly to add more than one.
+    pub fn named_parameter<S>(mut self, name: S, value: Value) -> Self
+    where
+        S: Into<String>,
+    {
+        self.named_parameters.insert(name.into(), value);
+        self
+    }
+
+    /// Sets the scan consistency to use for this query.
+    pub fn scan_consistency(mut self, consistency: ScanConsistency) -> Self {
+        self.scan_consistency = consistency;
+        self
+    }
+
+    /// Controls whether the statement is executed ad-hoc (the default) or prepared once
+    /// and reused from the client-side prepared statement cache on subsequent calls.
+    pub fn adhoc(mut self, adhoc: bool) -> Self {
+        self.adhoc = adhoc;
+        self
+    }
+
+    pub(crate) fn positional_parameters_ref(&self) -> &[Value] {
+        &self.positional_parameters
+    }
+
+    pub(crate) fn named_parameters_ref(&self) -> &HashMap<String, Value> {
+        &self.named_parameters
+    }
+
+    pub(crate) fn scan_consistency_value(&self) -> ScanConsistency {
+        self.scan_consistency
+    }
+
+    pub(crate) fn is_adhoc(&self) -> bool {
+        self.adhoc
+    }
+}
+
+/// Options which can be passed into `Bucket::analytics_query`/`SharedBucket::analytics_query`
+/// to customize how an analytics statement is executed.
+#[derive(Debug, Default, Clone)]
+pub struct AnalyticsOptions {}
+
+impl AnalyticsOptions {
+    pub fn new() -> Self {
+        AnalyticsOptions::default()
+    }
+}
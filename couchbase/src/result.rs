@@ -0,0 +1,177 @@
+use crate::error::{CouchbaseError, CouchbaseResult};
+use futures::channel::{mpsc, oneshot};
+use futures::stream::Stream;
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Returned from the KV mutation operations `upsert` and `remove`.
+#[derive(Debug, Clone)]
+pub struct MutationResult {
+    cas: u64,
+}
+
+impl MutationResult {
+    pub(crate) fn new(cas: u64) -> Self {
+        MutationResult { cas }
+    }
+
+    /// The CAS value assigned to the document by this mutation.
+    pub fn cas(&self) -> u64 {
+        self.cas
+    }
+}
+
+/// Returned from `Collection::get`, holding the raw content of the document.
+#[derive(Debug, Clone)]
+pub struct GetResult {
+    content: Vec<u8>,
+    cas: u64,
+}
+
+impl GetResult {
+    pub(crate) fn new(content: Vec<u8>, cas: u64) -> Self {
+        GetResult { content, cas }
+    }
+
+    /// The CAS value of the retrieved document.
+    pub fn cas(&self) -> u64 {
+        self.cas
+    }
+
+    /// Decodes the raw content into the requested type.
+    pub fn content<T>(&self) -> CouchbaseResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        serde_json::from_slice(&self.content).map_err(|e| CouchbaseError::Generic {
+            ctx: e.to_string(),
+        })
+    }
+}
+
+/// Metadata attached to a N1QL query once its row stream has been fully drained.
+#[derive(Debug, Clone, Default)]
+pub struct QueryMeta {
+    pub(crate) metrics: Option<Value>,
+    pub(crate) warnings: Vec<Value>,
+    pub(crate) status: String,
+}
+
+impl QueryMeta {
+    /// Query execution metrics, if the server returned any.
+    pub fn metrics(&self) -> Option<&Value> {
+        self.metrics.as_ref()
+    }
+
+    /// Any warnings the server attached to the response.
+    pub fn warnings(&self) -> &[Value] {
+        &self.warnings
+    }
+
+    /// The final status of the request (e.g. `"success"`).
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+}
+
+/// Holds the result of a N1QL query issued through `Bucket::query`/`SharedBucket::query`.
+///
+/// Rows are delivered incrementally as they come off libcouchbase's row callback rather
+/// than being buffered up front, so `rows()` should be drained (or dropped) before `meta()`
+/// resolves.
+pub struct QueryResult {
+    rows: mpsc::UnboundedReceiver<Value>,
+    meta: oneshot::Receiver<QueryMeta>,
+}
+
+impl QueryResult {
+    pub(crate) fn new(rows: mpsc::UnboundedReceiver<Value>, meta: oneshot::Receiver<QueryMeta>) -> Self {
+        QueryResult { rows, meta }
+    }
+
+    /// Streams the rows of the result as they arrive, decoded into the requested type.
+    pub fn rows<T>(&mut self) -> impl Stream<Item = CouchbaseResult<T>> + '_
+    where
+        T: DeserializeOwned,
+    {
+        (&mut self.rows).map(|row| {
+            serde_json::from_value(row).map_err(|e| CouchbaseError::Generic {
+                ctx: e.to_string(),
+            })
+        })
+    }
+
+    /// Resolves once the row stream has been fully drained, yielding the query's metrics,
+    /// warnings and final status.
+    pub async fn meta(self) -> CouchbaseResult<QueryMeta> {
+        self.meta.await.map_err(|_| CouchbaseError::Generic {
+            ctx: "query stream dropped before completion".into(),
+        })
+    }
+}
+
+/// Metadata attached to an analytics query once its row stream has been fully drained.
+#[derive(Debug, Clone, Default)]
+pub struct AnalyticsMeta {
+    pub(crate) metrics: Option<Value>,
+    pub(crate) warnings: Vec<Value>,
+    pub(crate) status: String,
+}
+
+impl AnalyticsMeta {
+    /// Query execution metrics, if the server returned any.
+    pub fn metrics(&self) -> Option<&Value> {
+        self.metrics.as_ref()
+    }
+
+    /// Any warnings the server attached to the response.
+    pub fn warnings(&self) -> &[Value] {
+        &self.warnings
+    }
+
+    /// The final status of the request (e.g. `"success"`).
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+}
+
+/// Holds the result of an analytics query issued through
+/// `Bucket::analytics_query`/`SharedBucket::analytics_query`.
+///
+/// Rows are delivered incrementally as they come off libcouchbase's row callback rather
+/// than being buffered up front, so `rows()` should be drained (or dropped) before `meta()`
+/// resolves.
+pub struct AnalyticsResult {
+    rows: mpsc::UnboundedReceiver<Value>,
+    meta: oneshot::Receiver<AnalyticsMeta>,
+}
+
+impl AnalyticsResult {
+    pub(crate) fn new(
+        rows: mpsc::UnboundedReceiver<Value>,
+        meta: oneshot::Receiver<AnalyticsMeta>,
+    ) -> Self {
+        AnalyticsResult { rows, meta }
+    }
+
+    /// Streams the rows of the result as they arrive, decoded into the requested type.
+    pub fn rows<T>(&mut self) -> impl Stream<Item = CouchbaseResult<T>> + '_
+    where
+        T: DeserializeOwned,
+    {
+        (&mut self.rows).map(|row| {
+            serde_json::from_value(row).map_err(|e| CouchbaseError::Generic {
+                ctx: e.to_string(),
+            })
+        })
+    }
+
+    /// Resolves once the row stream has been fully drained, yielding the query's metrics,
+    /// warnings and final status.
+    pub async fn meta(self) -> CouchbaseResult<AnalyticsMeta> {
+        self.meta.await.map_err(|_| CouchbaseError::Generic {
+            ctx: "analytics stream dropped before completion".into(),
+        })
+    }
+}
@@ -0,0 +1,62 @@
+use crate::collection::{Collection, SharedCollection};
+use crate::instance::{Instance, SharedInstance};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Provides access to the named collections living under a single scope.
+///
+/// Obtained via `Bucket::scope`. Couchbase Server 6.5+ groups collections into scopes;
+/// clusters without scope support only ever have the implicit `_default` scope, which is
+/// what `Bucket::default_collection` addresses directly.
+pub struct Scope {
+    instance: Rc<Instance>,
+    name: String,
+}
+
+impl Scope {
+    pub(crate) fn new(instance: Rc<Instance>, name: String) -> Self {
+        Scope { instance, name }
+    }
+
+    /// The name of this scope.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Opens the named collection living in this scope.
+    pub fn collection<S>(&self, name: S) -> Collection
+    where
+        S: Into<String>,
+    {
+        Collection::new(self.instance.clone(), Some(self.name.clone()), Some(name.into()))
+    }
+}
+
+/// Thread-safe counterpart of `Scope`, reachable from `SharedBucket::scope`.
+pub struct SharedScope {
+    instance: Arc<SharedInstance>,
+    name: String,
+}
+
+impl SharedScope {
+    pub(crate) fn new(instance: Arc<SharedInstance>, name: String) -> Self {
+        SharedScope { instance, name }
+    }
+
+    /// The name of this scope.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Opens the named collection living in this scope.
+    pub fn collection<S>(&self, name: S) -> SharedCollection
+    where
+        S: Into<String>,
+    {
+        SharedCollection::new(
+            self.instance.clone(),
+            Some(self.name.clone()),
+            Some(name.into()),
+        )
+    }
+}